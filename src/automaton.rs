@@ -6,11 +6,15 @@ use crossterm::style::StyledContent;
 
 // Local
 pub mod game_of_life;
+pub mod life_like;
+use crate::simulator::gpu::WasmNotSendSync;
 use crate::universe::CPUUniverse;
 
-pub trait AutomatonCell: Copy + Debug + Default + Eq + PartialEq + Send + Sync + 'static {
+pub trait AutomatonCell:
+    Copy + Debug + Default + Eq + PartialEq + WasmNotSendSync + 'static
+{
     type Neighbor;
-    type Encoded: Copy + Send + Sync;
+    type Encoded: Copy + WasmNotSendSync;
 
     fn encode(&self) -> Self::Encoded;
     fn decode(encoded: &Self::Encoded) -> Self;
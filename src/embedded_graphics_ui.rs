@@ -0,0 +1,33 @@
+// External libraries
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::Pixel;
+
+// CELL
+use crate::simulator::grid::{Grid, Position};
+use crate::simulator::CellularAutomaton;
+
+// Parallel to `crate::terminal_ui::TermDrawableAutomaton`, but for any
+// `embedded_graphics` `DrawTarget` instead of a terminal.
+pub trait DrawableAutomaton: CellularAutomaton {
+    fn color(&self, state: &Self::State) -> Rgb888;
+}
+
+pub fn render_frame<A, D>(
+    automaton: &A,
+    grid: &Grid<A::State>,
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    A: DrawableAutomaton,
+    D: DrawTarget<Color = Rgb888>,
+{
+    let dim = grid.dim();
+    let pixels = (0..dim.nb_rows).flat_map(|row| {
+        (0..dim.nb_cols).map(move |col| {
+            let state = grid.get(&Position::new(row, col));
+            Pixel(Point::new(col as i32, row as i32), automaton.color(state))
+        })
+    });
+    target.draw_iter(pixels)
+}
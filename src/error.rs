@@ -0,0 +1,54 @@
+// Standard library
+use std::fmt;
+
+// `Send + Sync` on native builds; relaxed on wasm32, where most `wgpu` handles are `!Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type ErrorSource = Box<dyn std::error::Error + Send + Sync + 'static>;
+#[cfg(target_arch = "wasm32")]
+pub type ErrorSource = Box<dyn std::error::Error + 'static>;
+
+#[derive(Debug)]
+pub enum Error {
+    IncompatibleMail,
+    InvalidGenerationRange { ref_gen: usize, target_gen: usize },
+    GpuSetup { source: ErrorSource },
+    RuleConfig { source: ErrorSource },
+    Persistence { source: ErrorSource },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IncompatibleMail => write!(
+                f,
+                "the received HistoryRequest is incompatible with the MailType it's included in"
+            ),
+            Error::InvalidGenerationRange {
+                ref_gen,
+                target_gen,
+            } => write!(
+                f,
+                "reference generation {} should not be larger than target generation {}",
+                ref_gen, target_gen
+            ),
+            Error::GpuSetup { source } => {
+                write!(f, "failed to set up the GPU pipeline: {}", source)
+            }
+            Error::RuleConfig { source } => write!(f, "failed to load rule config: {}", source),
+            Error::Persistence { source } => {
+                write!(f, "failed to save or load the history: {}", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::GpuSetup { source } => Some(source.as_ref()),
+            Error::RuleConfig { source } => Some(source.as_ref()),
+            Error::Persistence { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
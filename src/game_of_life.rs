@@ -1,16 +1,14 @@
 // Standard library
 use std::collections::HashMap;
-use std::sync::Arc;
 
 // External libraries
 use cascade::cascade;
 use crossterm::style::{style, Attribute, Color, StyledContent};
-use vulkano::descriptor::pipeline_layout::{PipelineLayout, PipelineLayoutAbstract};
-use vulkano::device::Device;
-use vulkano::pipeline::ComputePipeline;
+use embedded_graphics::pixelcolor::Rgb888;
 
 // CELL
-use crate::simulator::gpu::{GPUComputableAutomaton, PipelineInfo};
+use crate::embedded_graphics_ui::DrawableAutomaton;
+use crate::simulator::gpu::GPUComputableAutomaton;
 use crate::simulator::grid::{Grid, GridView, Position, RelCoords};
 use crate::simulator::{grid::Dimensions, CellularAutomaton};
 use crate::terminal_ui::TermDrawableAutomaton;
@@ -89,9 +87,22 @@ impl TermDrawableAutomaton for GameOfLife {
     }
 }
 
+impl DrawableAutomaton for GameOfLife {
+    fn color(&self, state: &States) -> Rgb888 {
+        match state {
+            States::Dead => Rgb888::new(40, 40, 40),
+            States::Alive => Rgb888::new(0, 200, 0),
+        }
+    }
+}
+
 impl GPUComputableAutomaton for GameOfLife {
-    type Pipeline = ComputePipeline<PipelineLayout<shader::Layout>>;
-    type PushConstants = shader::ty::Dim;
+    type State = States;
+    type Uniforms = Dim;
+
+    fn shader_source(&self) -> &'static str {
+        include_str!("game_of_life.wgsl")
+    }
 
     fn id_from_state(&self, state: &States) -> u32 {
         match state {
@@ -108,26 +119,23 @@ impl GPUComputableAutomaton for GameOfLife {
         }
     }
 
-    fn vk_setup(&self, device: &Arc<Device>) -> PipelineInfo<Self::Pipeline> {
-        let shader = shader::Shader::load(device.clone()).unwrap();
-        let pipeline =
-            ComputePipeline::new(device.clone(), &shader.main_entry_point(), &()).unwrap();
-        let layout = pipeline.layout().descriptor_set_layout(0).unwrap().clone();
-        PipelineInfo {
-            layout,
-            pipeline: Arc::new(pipeline),
-        }
-    }
-
-    fn push_constants(&self, grid: &Grid<Self::State>) -> Self::PushConstants {
+    fn uniforms(&self, grid: &Grid<Self::State>) -> Self::Uniforms {
         let dim = grid.dim();
-        shader::ty::Dim {
+        Dim {
             nb_rows: dim.nb_rows as u32,
             nb_cols: dim.nb_cols as u32,
         }
     }
 }
 
+/// Mirrors the `Dim` uniform block declared in `game_of_life.wgsl`.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Dim {
+    pub nb_rows: u32,
+    pub nb_cols: u32,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, std::hash::Hash)]
 pub enum States {
     Dead,
@@ -140,13 +148,6 @@ impl Default for States {
     }
 }
 
-mod shader {
-    vulkano_shaders::shader! {
-        ty: "compute",
-        path: "game_of_life.comp",
-    }
-}
-
 pub fn conway_canon() -> Grid<States> {
     let mut grid = Grid::new(Dimensions::new(100, 200));
     grid = cascade!(
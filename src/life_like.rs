@@ -0,0 +1,224 @@
+// Standard library
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// External libraries
+use crossterm::style::{style, Attribute, Color, StyledContent};
+use serde::Deserialize;
+
+// CELL
+use crate::error::Error;
+use crate::simulator::grid::{GridView, RelCoords};
+use crate::simulator::CellularAutomaton;
+use crate::terminal_ui::TermDrawableAutomaton;
+
+// Moore-neighborhood offsets shared by every Life-like rule.
+const NEIGHBORS: [RelCoords; 8] = [
+    RelCoords::new(-1, -1),
+    RelCoords::new(-1, 0),
+    RelCoords::new(-1, 1),
+    RelCoords::new(0, 1),
+    RelCoords::new(1, 1),
+    RelCoords::new(1, 0),
+    RelCoords::new(1, -1),
+    RelCoords::new(0, -1),
+];
+
+// Birth/survival neighbor counts and cell styles come from a TOML config
+// file rather than being hardcoded per-variant like `GameOfLife` is.
+pub struct LifeLike {
+    name: String,
+    birth: HashSet<u8>,
+    survive: HashSet<u8>,
+    style_map: HashMap<State, StyledContent<char>>,
+}
+
+impl LifeLike {
+    // Bypasses the config file; mostly useful for tests and for embedding a
+    // default rule in the binary.
+    pub fn new(
+        name: impl Into<String>,
+        birth: HashSet<u8>,
+        survive: HashSet<u8>,
+        style_map: HashMap<State, StyledContent<char>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            birth,
+            survive,
+            style_map,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path.as_ref())
+            .map_err(|err| Error::RuleConfig { source: Box::new(err) })?;
+        let config: RuleConfig = toml::from_str(&contents)
+            .map_err(|err| Error::RuleConfig { source: Box::new(err) })?;
+        Ok(config.into_life_like())
+    }
+
+    // Looks for `$XDG_CONFIG_HOME/rna/rules/<name>.toml` (falling back to
+    // `~/.config/rna/rules/<name>.toml`).
+    pub fn from_config(name: &str) -> Result<Self, Error> {
+        let path = Self::rules_dir()?.join(format!("{}.toml", name));
+        Self::from_file(path)
+    }
+
+    pub fn list_configs() -> Result<Vec<String>, Error> {
+        let dir = Self::rules_dir()?;
+        let entries =
+            fs::read_dir(&dir).map_err(|err| Error::RuleConfig { source: Box::new(err) })?;
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .map(String::from)
+                } else {
+                    None
+                }
+            })
+            .collect())
+    }
+
+    fn rules_dir() -> Result<PathBuf, Error> {
+        dirs::config_dir()
+            .map(|dir| dir.join("rna").join("rules"))
+            .ok_or_else(|| Error::RuleConfig {
+                source: Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "could not locate the XDG config directory",
+                )),
+            })
+    }
+}
+
+impl CellularAutomaton for LifeLike {
+    type State = State;
+
+    fn update_cpu<'a>(&self, grid: &GridView<'a, Self::State>) -> Self::State {
+        let nb_alive_neighbors = grid
+            .get_multiple(NEIGHBORS.to_vec())
+            .iter()
+            .filter(|cell| matches!(cell, State::Alive))
+            .count() as u8;
+
+        match grid.state() {
+            State::Dead => {
+                if self.birth.contains(&nb_alive_neighbors) {
+                    State::Alive
+                } else {
+                    State::Dead
+                }
+            }
+            State::Alive => {
+                if self.survive.contains(&nb_alive_neighbors) {
+                    State::Alive
+                } else {
+                    State::Dead
+                }
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TermDrawableAutomaton for LifeLike {
+    fn style(&self, state: &State) -> &StyledContent<char> {
+        self.style_map
+            .get(state)
+            .unwrap_or_else(|| panic!("no style configured for state {:?}", state))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, std::hash::Hash)]
+pub enum State {
+    Dead,
+    Alive,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::Dead
+    }
+}
+
+#[derive(Deserialize)]
+struct RuleConfig {
+    name: String,
+    birth: HashSet<u8>,
+    survive: HashSet<u8>,
+    styles: StylesConfig,
+}
+
+#[derive(Deserialize)]
+struct StylesConfig {
+    dead: CellStyleConfig,
+    alive: CellStyleConfig,
+}
+
+#[derive(Deserialize)]
+struct CellStyleConfig {
+    glyph: char,
+    color: ColorConfig,
+    #[serde(default)]
+    bold: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ColorConfig {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl From<ColorConfig> for Color {
+    fn from(config: ColorConfig) -> Self {
+        match config {
+            ColorConfig::Black => Color::Black,
+            ColorConfig::Red => Color::Red,
+            ColorConfig::Green => Color::Green,
+            ColorConfig::Yellow => Color::Yellow,
+            ColorConfig::Blue => Color::Blue,
+            ColorConfig::Magenta => Color::Magenta,
+            ColorConfig::Cyan => Color::Cyan,
+            ColorConfig::White => Color::White,
+            ColorConfig::Grey => Color::Grey,
+        }
+    }
+}
+
+impl RuleConfig {
+    fn into_life_like(self) -> LifeLike {
+        let mut style_map = HashMap::new();
+        style_map.insert(State::Dead, self.styles.dead.into_styled_content());
+        style_map.insert(State::Alive, self.styles.alive.into_styled_content());
+
+        LifeLike::new(self.name, self.birth, self.survive, style_map)
+    }
+}
+
+impl CellStyleConfig {
+    fn into_styled_content(self) -> StyledContent<char> {
+        let styled = style(self.glyph).with(self.color.into());
+        if self.bold {
+            styled.attribute(Attribute::Bold)
+        } else {
+            styled
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// Standard library
+use std::sync::Arc;
+
+// External libraries
+use wgpu::{BindGroupLayout, ComputePipeline, Device, Queue};
+
+// CELL
+use crate::error::Error;
+use crate::simulator::grid::Grid;
+
+// Collapses to `Send + Sync` on native targets and to a no-op bound on
+// `wasm32`, where most `wgpu` handle types are `!Send`. `AutomatonCell` (and
+// so `GPUCell`, through it) and `GPUComputableAutomaton::State` are bound on
+// this instead of `Send + Sync` directly so the same trait definitions
+// compile for both.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait WasmNotSendSync: Send + Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + Sync> WasmNotSendSync for T {}
+
+#[cfg(target_arch = "wasm32")]
+pub trait WasmNotSendSync {}
+#[cfg(target_arch = "wasm32")]
+impl<T> WasmNotSendSync for T {}
+
+pub struct PipelineInfo {
+    pub layout: BindGroupLayout,
+    pub pipeline: Arc<ComputePipeline>,
+}
+
+pub trait GPUComputableAutomaton: Sized {
+    type State: Default + WasmNotSendSync;
+    // Replaces the old Vulkan backend's `push_constants`, since WebGPU's push constant budget
+    // is too small to rely on.
+    type Uniforms: bytemuck::Pod + bytemuck::Zeroable;
+
+    fn shader_source(&self) -> &'static str;
+
+    fn id_from_state(&self, state: &Self::State) -> u32;
+    fn state_from_id(&self, id: u32) -> Self::State;
+
+    // Validated through a `wgpu` error scope instead of letting a malformed shader take the
+    // whole device down. `async` so wasm32 callers can drive it with `wasm_bindgen_futures`
+    // instead of `pollster::block_on`, which needs real thread parking; native callers can
+    // just `pollster::block_on` this method themselves.
+    async fn setup_pipeline(&self, device: &Device) -> Result<PipelineInfo, Error> {
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("GPUComputableAutomaton shader"),
+            source: wgpu::ShaderSource::Wgsl(self.shader_source().into()),
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("GPUComputableAutomaton bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("GPUComputableAutomaton pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("GPUComputableAutomaton compute pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point: "main",
+        });
+
+        if let Some(err) = device.pop_error_scope().await {
+            return Err(Error::GpuSetup {
+                source: Box::new(err),
+            });
+        }
+
+        Ok(PipelineInfo {
+            layout,
+            pipeline: Arc::new(pipeline),
+        })
+    }
+
+    fn uniforms(&self, grid: &Grid<Self::State>) -> Self::Uniforms;
+}
+
+pub type GPUContext = (Arc<Device>, Arc<Queue>);
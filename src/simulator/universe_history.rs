@@ -1,17 +1,31 @@
 // Standard library
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
 use std::thread;
 
+// External libraries
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
 // CELL
 use crate::{
     advanced_channels::{MailType, SlaveEndpoint},
+    error::Error,
     universe::{Universe, UniverseDiff},
 };
 
+/// Erases the `U: Serialize` bound needed to write a `LogEntry<U>` so that
+/// `UniverseHistory<U>` itself doesn't have to require `Serialize` just to
+/// support the (optional) streaming log.
+type LogWriter<U> = Box<dyn FnMut(&LogEntry<U>) -> Result<(), Error> + Send>;
+
 pub struct UniverseHistory<U: Universe> {
     diffs: Vec<U::Diff>,
     checkpoints: Vec<U>,
     f_check: usize,
     last: U,
+    log: Option<LogWriter<U>>,
 }
 
 impl<U: Universe> UniverseHistory<U> {
@@ -21,22 +35,34 @@ impl<U: Universe> UniverseHistory<U> {
             checkpoints: vec![start_universe.clone()],
             f_check,
             last: start_universe,
+            log: None,
         }
     }
 
-    pub fn push(&mut self, universe: U) {
+    pub fn push(&mut self, universe: U) -> Result<(), Error> {
         let diff = self.last.diff(&universe);
         self.diffs.push(diff);
-        if self.f_check != 0 && self.diffs.len() % self.f_check == 0 {
+        let new_checkpoint = self.f_check != 0 && self.diffs.len() % self.f_check == 0;
+        if new_checkpoint {
             self.checkpoints.push(universe.clone());
         }
         self.last = universe;
+
+        if let Some(write_entry) = &mut self.log {
+            let last_diff = self.diffs.last().expect("a diff was just pushed");
+            write_entry(&LogEntry::Diff(last_diff.clone()))?;
+            if new_checkpoint {
+                let checkpoint = self.checkpoints.last().expect("a checkpoint was just pushed");
+                write_entry(&LogEntry::Checkpoint(checkpoint.clone()))?;
+            }
+        }
+        Ok(())
     }
 
-    pub fn get_gen(&self, gen: usize) -> Option<U> {
+    pub fn get_gen(&self, gen: usize) -> Result<Option<U>, Error> {
         if self.diffs.len() < gen {
             // We don't have that generation
-            None
+            Ok(None)
         } else {
             // We have the generation
             if self.f_check != 0 {
@@ -45,93 +71,339 @@ impl<U: Universe> UniverseHistory<U> {
 
                 // Accumulate differences between reference grid and target generation
                 let stacked_diffs = U::Diff::stack_mul(&self.diffs[(gen - shift)..gen]);
-                Some(
+                Ok(Some(
                     self.checkpoints[idx as usize]
                         .clone()
                         .apply_diff(&stacked_diffs),
-                )
+                ))
             } else {
                 // Accumulate differences between initial grid and target generation
                 let stacked_diffs = U::Diff::stack_mul(&self.diffs[0..gen]);
-                Some(self.checkpoints[0].clone().apply_diff(&stacked_diffs))
+                Ok(Some(self.checkpoints[0].clone().apply_diff(&stacked_diffs)))
             }
         }
     }
 
-    pub fn get_diff(&self, ref_gen: usize, target_gen: usize) -> Option<U::Diff> {
+    pub fn get_diff(&self, ref_gen: usize, target_gen: usize) -> Result<Option<U::Diff>, Error> {
         if target_gen < ref_gen {
-            panic!(ERR_INCORRECT_DIFF);
+            return Err(Error::InvalidGenerationRange {
+                ref_gen,
+                target_gen,
+            });
         }
         if self.diffs.len() < target_gen {
-            None
+            Ok(None)
         } else {
-            Some(U::Diff::stack_mul(&self.diffs[ref_gen..target_gen]))
+            Ok(Some(U::Diff::stack_mul(&self.diffs[ref_gen..target_gen])))
         }
     }
 
+    /// Starts (or resumes) streaming new generations to `path` as they are
+    /// [`push`](Self::push)ed, instead of requiring a full [`save`](Self::save)
+    /// for every generation. Truncates any existing file at `path` and
+    /// writes the whole in-memory history as the new log's starting point.
+    pub fn open_log(&mut self, path: impl AsRef<Path>) -> Result<(), Error>
+    where
+        U: Serialize,
+        U::Diff: Serialize,
+    {
+        let file = File::create(path).map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        bincode::serialize_into(&mut writer, &self.f_check).map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+        bincode::serialize_into(&mut writer, &self.checkpoints[0]).map_err(|err| {
+            Error::Persistence {
+                source: Box::new(err),
+            }
+        })?;
+
+        let mut checkpoint_idx = 1;
+        for (i, diff) in self.diffs.iter().enumerate() {
+            bincode::serialize_into(&mut writer, &LogEntry::Diff(diff.clone())).map_err(
+                |err| Error::Persistence {
+                    source: Box::new(err),
+                },
+            )?;
+            if self.f_check != 0 && (i + 1) % self.f_check == 0 {
+                let checkpoint = LogEntry::Checkpoint(self.checkpoints[checkpoint_idx].clone());
+                bincode::serialize_into(&mut writer, &checkpoint).map_err(|err| {
+                    Error::Persistence {
+                        source: Box::new(err),
+                    }
+                })?;
+                checkpoint_idx += 1;
+            }
+        }
+        writer.flush().map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+
+        self.log = Some(Box::new(move |entry: &LogEntry<U>| {
+            bincode::serialize_into(&mut writer, entry).map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+            writer.flush().map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })
+        }));
+        Ok(())
+    }
+
+    /// Writes the whole history to `path` in one shot. Prefer [`open_log`](Self::open_log)
+    /// for long runs, where rewriting the entire history on every save would
+    /// dominate the cost of a million-generation run.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error>
+    where
+        U: Serialize,
+        U::Diff: Serialize,
+    {
+        let file = File::create(path).map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+        bincode::serialize_into(
+            BufWriter::new(file),
+            &HistorySnapshot {
+                f_check: self.f_check,
+                checkpoints: &self.checkpoints,
+                diffs: &self.diffs,
+            },
+        )
+        .map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })
+    }
+
+    /// Restores a history previously written by [`save`](Self::save). The
+    /// returned history is not attached to a streaming log; call
+    /// [`open_log`](Self::open_log) again if you want to keep appending to
+    /// disk incrementally.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error>
+    where
+        U: DeserializeOwned,
+        U::Diff: DeserializeOwned,
+    {
+        let file = File::open(path).map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+        let snapshot: OwnedHistorySnapshot<U> = bincode::deserialize_from(BufReader::new(file))
+            .map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+        Self::from_snapshot(snapshot.f_check, snapshot.checkpoints, snapshot.diffs)
+    }
+
+    /// Restores a history previously (or still being) written by
+    /// [`open_log`](Self::open_log), and keeps appending to the same file as
+    /// further generations are [`push`](Self::push)ed.
+    pub fn load_log(path: impl AsRef<Path>) -> Result<Self, Error>
+    where
+        U: Serialize + DeserializeOwned,
+        U::Diff: Serialize + DeserializeOwned,
+    {
+        let read_file = File::open(path.as_ref()).map_err(|err| Error::Persistence {
+            source: Box::new(err),
+        })?;
+        let mut reader = BufReader::new(read_file);
+
+        let f_check: usize =
+            bincode::deserialize_from(&mut reader).map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+        let first_checkpoint: U =
+            bincode::deserialize_from(&mut reader).map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+
+        let mut checkpoints = vec![first_checkpoint];
+        let mut diffs = vec![];
+        loop {
+            // Peek instead of deserializing blind: an empty buffer here means
+            // we stopped exactly on a record boundary, i.e. a clean end of
+            // log. Anything else handed to `deserialize_from` that still
+            // fails is a genuinely corrupt or truncated trailing record, and
+            // must be reported rather than silently dropped.
+            match reader.fill_buf() {
+                Ok(buf) if buf.is_empty() => break,
+                Ok(_) => {}
+                Err(err) => {
+                    return Err(Error::Persistence {
+                        source: Box::new(err),
+                    })
+                }
+            }
+
+            let entry: LogEntry<U> =
+                bincode::deserialize_from(&mut reader).map_err(|err| Error::Persistence {
+                    source: Box::new(err),
+                })?;
+            match entry {
+                LogEntry::Diff(diff) => diffs.push(diff),
+                LogEntry::Checkpoint(checkpoint) => checkpoints.push(checkpoint),
+            }
+        }
+
+        let mut history = Self::from_snapshot(f_check, checkpoints, diffs)?;
+
+        let append_file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+        let mut writer = BufWriter::new(append_file);
+        history.log = Some(Box::new(move |entry: &LogEntry<U>| {
+            bincode::serialize_into(&mut writer, entry).map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })?;
+            writer.flush().map_err(|err| Error::Persistence {
+                source: Box::new(err),
+            })
+        }));
+
+        Ok(history)
+    }
+
+    fn from_snapshot(
+        f_check: usize,
+        checkpoints: Vec<U>,
+        diffs: Vec<U::Diff>,
+    ) -> Result<Self, Error> {
+        // Same indexing as `get_gen(diffs.len())`: with `f_check != 0`, the
+        // checkpoint/diff split lines up exactly on the last generation,
+        // including the no-op case where `diffs.len()` is itself a multiple
+        // of `f_check` (the trailing diff slice is then empty). A log torn
+        // between a diff's write and its matching checkpoint's leaves
+        // `checkpoints` one short of what `idx` expects, so this is checked
+        // rather than indexed into directly.
+        let last = if f_check != 0 {
+            let gen = diffs.len();
+            let idx = gen / f_check;
+            let shift = gen % f_check;
+            let checkpoint = checkpoints.get(idx).ok_or_else(|| Error::Persistence {
+                source: Box::new(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "log ended after a checkpoint-boundary diff but before its checkpoint",
+                )),
+            })?;
+            let stacked_diffs = U::Diff::stack_mul(&diffs[(gen - shift)..gen]);
+            checkpoint.clone().apply_diff(&stacked_diffs)
+        } else {
+            let stacked_diffs = U::Diff::stack_mul(&diffs);
+            checkpoints[0].clone().apply_diff(&stacked_diffs)
+        };
+
+        Ok(Self {
+            diffs,
+            checkpoints,
+            f_check,
+            last,
+            log: None,
+        })
+    }
+
     pub fn detach(mut self, endpoint: SlaveEndpoint<HistoryResponse<U>, HistoryRequest<U>>) {
         thread::spawn(move || loop {
             match endpoint.wait_for_mail() {
                 MailType::Message(msg, None) => match msg {
-                    HistoryRequest::Push(grid) => self.push(grid),
-                    _ => panic!(ERR_INCOMPATIBLE_MAIL_TYPE),
+                    HistoryRequest::Push(grid) => {
+                        // No reply channel to report a persistence failure on; stop
+                        // logging instead of appending onto a now-desynced stream.
+                        if self.push(grid).is_err() {
+                            self.log = None;
+                        }
+                    }
+                    _ => {}
                 },
                 MailType::Message(msg, Some(req)) => match msg {
                     HistoryRequest::GetGen(gen, blocking) => match self.get_gen(gen) {
-                        Some(grid) => {
+                        Ok(Some(grid)) => {
                             req.respond(HistoryResponse::GetGen(Some(grid)));
                         }
-                        None => {
+                        Ok(None) => {
                             if blocking {
                                 loop {
                                     match endpoint.wait_for_msg() {
                                         HistoryRequest::Push(grid) => {
-                                            self.push(grid);
-                                            if let Some(response_grid) = self.get_gen(gen) {
-                                                req.respond(HistoryResponse::GetGen(Some(
-                                                    response_grid,
-                                                )));
+                                            if let Err(err) = self.push(grid) {
+                                                req.respond(HistoryResponse::Error(err));
                                                 break;
                                             }
+                                            match self.get_gen(gen) {
+                                                Ok(Some(response_grid)) => {
+                                                    req.respond(HistoryResponse::GetGen(Some(
+                                                        response_grid,
+                                                    )));
+                                                    break;
+                                                }
+                                                Ok(None) => continue,
+                                                Err(err) => {
+                                                    req.respond(HistoryResponse::Error(err));
+                                                    break;
+                                                }
+                                            }
+                                        }
+                                        _ => {
+                                            req.respond(HistoryResponse::Error(
+                                                Error::IncompatibleMail,
+                                            ));
+                                            break;
                                         }
-                                        _ => panic!(ERR_INCOMPATIBLE_MAIL_TYPE),
                                     }
                                 }
                             } else {
                                 req.respond(HistoryResponse::GetGen(None));
                             }
                         }
+                        Err(err) => req.respond(HistoryResponse::Error(err)),
                     },
                     HistoryRequest::GetDiff(ref_gen, target_gen, blocking) => {
                         match self.get_diff(ref_gen, target_gen) {
-                            Some(diff) => {
+                            Ok(Some(diff)) => {
                                 req.respond(HistoryResponse::GetDiff(Some(diff)));
                             }
-                            None => {
+                            Ok(None) => {
                                 if blocking {
                                     loop {
                                         match endpoint.wait_for_msg() {
                                             HistoryRequest::Push(grid) => {
-                                                self.push(grid);
-                                                if let Some(response_diff) =
-                                                    self.get_diff(ref_gen, target_gen)
-                                                {
-                                                    req.respond(HistoryResponse::GetDiff(Some(
-                                                        response_diff,
-                                                    )));
+                                                if let Err(err) = self.push(grid) {
+                                                    req.respond(HistoryResponse::Error(err));
                                                     break;
                                                 }
+                                                match self.get_diff(ref_gen, target_gen) {
+                                                    Ok(Some(response_diff)) => {
+                                                        req.respond(HistoryResponse::GetDiff(
+                                                            Some(response_diff),
+                                                        ));
+                                                        break;
+                                                    }
+                                                    Ok(None) => continue,
+                                                    Err(err) => {
+                                                        req.respond(HistoryResponse::Error(err));
+                                                        break;
+                                                    }
+                                                }
+                                            }
+                                            _ => {
+                                                req.respond(HistoryResponse::Error(
+                                                    Error::IncompatibleMail,
+                                                ));
+                                                break;
                                             }
-                                            _ => panic!(ERR_INCOMPATIBLE_MAIL_TYPE),
                                         }
                                     }
                                 } else {
-                                    req.respond(HistoryResponse::GetGen(None));
+                                    req.respond(HistoryResponse::GetDiff(None));
                                 }
                             }
+                            Err(err) => req.respond(HistoryResponse::Error(err)),
                         }
                     }
-                    _ => panic!(ERR_INCOMPATIBLE_MAIL_TYPE),
+                    _ => req.respond(HistoryResponse::Error(Error::IncompatibleMail)),
                 },
                 MailType::DeadChannel => break,
             }
@@ -148,8 +420,35 @@ pub enum HistoryRequest<U: Universe> {
 pub enum HistoryResponse<U: Universe> {
     GetDiff(Option<U::Diff>),
     GetGen(Option<U>),
+    Error(Error),
+}
+
+/// Whole-history container written by [`UniverseHistory::save`].
+#[derive(Serialize)]
+#[serde(bound(serialize = "U: Serialize, U::Diff: Serialize"))]
+struct HistorySnapshot<'a, U: Universe> {
+    f_check: usize,
+    checkpoints: &'a [U],
+    diffs: &'a [U::Diff],
 }
 
-const ERR_INCORRECT_DIFF: &str = "Base generation should be smaller than target generation.";
-const ERR_INCOMPATIBLE_MAIL_TYPE: &str =
-    "The received HistoryRequest is incompatible with the MailType it's included in.";
+/// Owned counterpart of [`HistorySnapshot`], produced by [`UniverseHistory::load`].
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "U: DeserializeOwned, U::Diff: DeserializeOwned"))]
+struct OwnedHistorySnapshot<U: Universe> {
+    f_check: usize,
+    checkpoints: Vec<U>,
+    diffs: Vec<U::Diff>,
+}
+
+/// One record in the streaming log written by [`UniverseHistory::open_log`],
+/// following the initial `(f_check, checkpoints[0])` header.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "U: Serialize, U::Diff: Serialize",
+    deserialize = "U: DeserializeOwned, U::Diff: DeserializeOwned"
+))]
+enum LogEntry<U: Universe> {
+    Diff(U::Diff),
+    Checkpoint(U),
+}